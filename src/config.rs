@@ -0,0 +1,190 @@
+/// OBD-II protocol to request via `ATSPn`.
+///
+/// `Automatic` lets the ELM327 auto-detect the protocol used by the vehicle; the other variants
+/// force a specific protocol, which is faster if it is already known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// `ATSP0` - Automatic protocol detection.
+    Automatic,
+    /// `ATSP1` - SAE J1850 PWM.
+    SaeJ1850Pwm,
+    /// `ATSP2` - SAE J1850 VPW.
+    SaeJ1850Vpw,
+    /// `ATSP3` - ISO 9141-2.
+    Iso9141_2,
+    /// `ATSP4` - ISO 14230-4 KWP (5 baud init).
+    Iso14230_4Kwp5BaudInit,
+    /// `ATSP5` - ISO 14230-4 KWP (fast init).
+    Iso14230_4KwpFastInit,
+    /// `ATSP6` - ISO 15765-4 CAN (11 bit ID, 500 kbaud).
+    Iso15765_4Can11Bit500k,
+    /// `ATSP7` - ISO 15765-4 CAN (29 bit ID, 500 kbaud).
+    Iso15765_4Can29Bit500k,
+    /// `ATSP8` - ISO 15765-4 CAN (11 bit ID, 250 kbaud).
+    Iso15765_4Can11Bit250k,
+    /// `ATSP9` - ISO 15765-4 CAN (29 bit ID, 250 kbaud).
+    Iso15765_4Can29Bit250k,
+    /// `ATSPA` - SAE J1939 CAN.
+    SaeJ1939Can,
+}
+
+impl Protocol {
+    /// The `ATSPn` command used to select this protocol.
+    fn command(&self) -> &'static str {
+        match self {
+            Protocol::Automatic => "ATSP0",
+            Protocol::SaeJ1850Pwm => "ATSP1",
+            Protocol::SaeJ1850Vpw => "ATSP2",
+            Protocol::Iso9141_2 => "ATSP3",
+            Protocol::Iso14230_4Kwp5BaudInit => "ATSP4",
+            Protocol::Iso14230_4KwpFastInit => "ATSP5",
+            Protocol::Iso15765_4Can11Bit500k => "ATSP6",
+            Protocol::Iso15765_4Can29Bit500k => "ATSP7",
+            Protocol::Iso15765_4Can11Bit250k => "ATSP8",
+            Protocol::Iso15765_4Can29Bit250k => "ATSP9",
+            Protocol::SaeJ1939Can => "ATSPA",
+        }
+    }
+
+    /// Returns `true` if this protocol is one of the CAN variants (ISO 15765-4 or SAE J1939) or
+    /// `Automatic`, which is assumed to resolve to CAN on modern vehicles.
+    ///
+    /// Mode 03/07/0A DTC responses on CAN protocols are prefixed with a count-of-codes byte after
+    /// the mode echo; legacy protocols (SAE J1850, ISO 9141-2, ISO 14230-4 KWP) have no such byte.
+    pub(crate) fn has_dtc_count_byte(&self) -> bool {
+        !matches!(
+            self,
+            Protocol::SaeJ1850Pwm
+                | Protocol::SaeJ1850Vpw
+                | Protocol::Iso9141_2
+                | Protocol::Iso14230_4Kwp5BaudInit
+                | Protocol::Iso14230_4KwpFastInit
+        )
+    }
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Protocol::Automatic
+    }
+}
+
+/// Adaptive timing mode, controlling how the ELM327 adjusts its response timeout to the vehicle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptiveTiming {
+    /// `ATAT0` - Adaptive timing off.
+    Off,
+    /// `ATAT1` - Adaptive timing, mode 1 (recommended default).
+    Auto1,
+    /// `ATAT2` - Adaptive timing, mode 2 (more aggressive).
+    Auto2,
+}
+
+impl AdaptiveTiming {
+    /// The `ATATn` command used to select this adaptive timing mode.
+    fn command(&self) -> &'static str {
+        match self {
+            AdaptiveTiming::Off => "ATAT0",
+            AdaptiveTiming::Auto1 => "ATAT1",
+            AdaptiveTiming::Auto2 => "ATAT2",
+        }
+    }
+}
+
+/// Configuration for the ELM327's initialisation/protocol handshake, used by
+/// [`Elm327::from_path`](crate::Elm327::from_path) and
+/// [`Elm327::configure`](crate::Elm327::configure) to replace manually issuing `AT` commands
+/// after connecting.
+///
+/// Build one with [`Elm327Config::new`] and the fluent `with_*` setters, then pass it to
+/// `from_path`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use elm327::config::{Elm327Config, Protocol};
+/// let config = Elm327Config::new()
+///     .with_protocol(Protocol::Iso15765_4Can11Bit500k)
+///     .with_headers(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elm327Config {
+    protocol: Protocol,
+    echo: bool,
+    headers: bool,
+    spaces: bool,
+    adaptive_timing: AdaptiveTiming,
+}
+
+impl Elm327Config {
+    /// Builds a config with the library's default settings: automatic protocol detection, echo
+    /// off, headers off, spaces on (required for the byte-splitting used throughout this crate)
+    /// and adaptive timing mode 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the protocol requested via `ATSPn`.
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Sets whether the device should echo commands back (`ATE1`) or not (`ATE0`).
+    pub fn with_echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    /// Sets whether responses should be prefixed with the CAN arbitration ID header (`ATH1`) or
+    /// not (`ATH0`). Headers are required for [`isotp::reassemble`](crate::isotp::reassemble).
+    pub fn with_headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets whether response bytes should be space-separated (`ATS1`) or not (`ATS0`).
+    ///
+    /// This crate's parsing (see [`ObdPacket::new`](crate::packet::ObdPacket::new)) expects
+    /// space-separated bytes, so this should only be disabled if the response is being handled
+    /// some other way.
+    pub fn with_spaces(mut self, spaces: bool) -> Self {
+        self.spaces = spaces;
+        self
+    }
+
+    /// Sets the adaptive timing mode used via `ATATn`.
+    pub fn with_adaptive_timing(mut self, adaptive_timing: AdaptiveTiming) -> Self {
+        self.adaptive_timing = adaptive_timing;
+        self
+    }
+
+    /// The protocol this configuration requests.
+    pub(crate) fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// The ordered sequence of `AT` commands needed to apply this configuration.
+    pub(crate) fn commands(&self) -> Vec<&'static str> {
+        vec![
+            if self.echo { "ATE1" } else { "ATE0" },
+            "ATL0",
+            if self.spaces { "ATS1" } else { "ATS0" },
+            if self.headers { "ATH1" } else { "ATH0" },
+            self.protocol.command(),
+            self.adaptive_timing.command(),
+        ]
+    }
+}
+
+impl Default for Elm327Config {
+    fn default() -> Self {
+        Self {
+            protocol: Protocol::Automatic,
+            echo: false,
+            headers: false,
+            spaces: true,
+            adaptive_timing: AdaptiveTiming::Auto1,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::config::Protocol;
+use crate::error::{Error, Result};
+
+/// A decoded Diagnostic Trouble Code, e.g. `P0143`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dtc {
+    /// The system letter - `P` (powertrain), `C` (chassis), `B` (body) or `U` (network).
+    pub system: char,
+    /// The four digit code following the system letter, e.g. `0143`.
+    pub code: [u8; 4],
+}
+
+impl Dtc {
+    /// Decodes a single DTC from its two raw bytes.
+    ///
+    /// The top two bits of the first byte select the system letter, the next two bits give the
+    /// first digit (`0`-`3`), and the remaining three nibbles are hex digits, e.g. `0x01 0x43`
+    /// decodes to `P0143`.
+    ///
+    /// # Parameters
+    ///
+    /// * `hi` - The first byte.
+    /// * `lo` - The second byte.
+    fn decode(hi: u8, lo: u8) -> Self {
+        let system = match hi >> 6 {
+            0b00 => 'P',
+            0b01 => 'C',
+            0b10 => 'B',
+            _ => 'U',
+        };
+
+        let digits = [
+            (hi >> 4) & 0b11,
+            hi & 0xF,
+            lo >> 4,
+            lo & 0xF,
+        ];
+
+        Dtc {
+            system,
+            code: digits,
+        }
+    }
+}
+
+impl std::fmt::Display for Dtc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{:01X}{:01X}{:01X}{:01X}",
+            self.system, self.code[0], self.code[1], self.code[2], self.code[3]
+        )
+    }
+}
+
+/// Which set of DTCs to query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DtcMode {
+    /// Mode `03` - stored DTCs.
+    Stored,
+    /// Mode `07` - pending DTCs.
+    Pending,
+    /// Mode `0A` - permanent DTCs.
+    Permanent,
+}
+
+impl DtcMode {
+    /// The command string sent to the device for this mode, e.g. `"03"`.
+    pub(crate) fn command(&self) -> &'static str {
+        match self {
+            DtcMode::Stored => "03",
+            DtcMode::Pending => "07",
+            DtcMode::Permanent => "0A",
+        }
+    }
+
+    /// The mode-echo byte expected at the start of the response, e.g. `"43"` for stored DTCs.
+    fn echo(&self) -> &'static str {
+        match self {
+            DtcMode::Stored => "43",
+            DtcMode::Pending => "47",
+            DtcMode::Permanent => "4A",
+        }
+    }
+}
+
+/// Parses the response lines from a DTC query into a list of [`Dtc`]s.
+///
+/// The ELM327 reassembles multi-frame CAN responses itself before printing them, so the mode-echo
+/// byte (and, on CAN protocols, the count byte that follows it) only ever appears once, on the
+/// first line of the response; legacy protocols (SAE J1850, ISO 9141-2, ISO 14230-4 KWP) have no
+/// count byte, only the mode echo. A DTC list long enough to wrap the adapter's output continues
+/// on subsequent lines as bare data bytes, with no echo or count repeated, so every line from the
+/// first match onwards is treated as part of the same response. Padding pairs of `00 00` mean "no
+/// code" and are skipped.
+///
+/// # Parameters
+///
+/// * `mode` - The DTC mode that was queried, used to match the echo byte.
+/// * `protocol` - The protocol currently in use, used to decide whether a count byte is present.
+/// * `lines` - Response lines as returned by [`Elm327::write`](crate::Elm327::write).
+pub(crate) fn parse_response(
+    mode: DtcMode,
+    protocol: Protocol,
+    lines: &[String],
+) -> Result<Vec<Dtc>> {
+    let skip = if protocol.has_dtc_count_byte() { 2 } else { 1 };
+
+    let start = lines
+        .iter()
+        .position(|line| line.trim().to_uppercase().starts_with(mode.echo()))
+        .ok_or(Error::Packet("No DTC response line found."))?;
+
+    let mut bytes = Vec::new();
+
+    for (i, line) in lines[start..].iter().enumerate() {
+        let tokens = line.trim().split(' ').collect::<Vec<_>>();
+        let tokens = if i == 0 { &tokens[skip.min(tokens.len())..] } else { &tokens[..] };
+
+        for token in tokens {
+            bytes.push(u8::from_str_radix(token, 16).map_err(|_| Error::Conversion)?);
+        }
+    }
+
+    Ok(bytes
+        .chunks(2)
+        .filter(|pair| pair.len() == 2 && (pair[0], pair[1]) != (0, 0))
+        .map(|pair| Dtc::decode(pair[0], pair[1]))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_single_dtc() {
+        assert_eq!(
+            Dtc::decode(0x01, 0x43),
+            Dtc {
+                system: 'P',
+                code: [0, 1, 4, 3]
+            }
+        );
+    }
+
+    #[test]
+    fn dtc_displays_as_canonical_string() {
+        assert_eq!(Dtc::decode(0x01, 0x43).to_string(), "P0143");
+    }
+
+    #[test]
+    fn parses_count_byte_on_can_protocol() {
+        let lines = vec!["43 01 01 43".to_string()];
+        let dtcs = parse_response(DtcMode::Stored, Protocol::Iso15765_4Can11Bit500k, &lines)
+            .unwrap();
+
+        assert_eq!(dtcs, vec![Dtc::decode(0x01, 0x43)]);
+    }
+
+    #[test]
+    fn no_count_byte_on_legacy_protocol() {
+        let lines = vec!["43 01 43".to_string()];
+        let dtcs = parse_response(DtcMode::Stored, Protocol::Iso9141_2, &lines).unwrap();
+
+        assert_eq!(dtcs, vec![Dtc::decode(0x01, 0x43)]);
+    }
+
+    #[test]
+    fn concatenates_dtcs_spanning_multiple_lines() {
+        // Only the first line carries the mode echo and count byte; the ELM327 wraps the rest
+        // of a long response onto subsequent lines as bare continuation bytes.
+        let lines = vec!["43 02 01 43".to_string(), "02 14".to_string()];
+        let dtcs = parse_response(DtcMode::Stored, Protocol::Iso15765_4Can11Bit500k, &lines)
+            .unwrap();
+
+        assert_eq!(dtcs, vec![Dtc::decode(0x01, 0x43), Dtc::decode(0x02, 0x14)]);
+    }
+
+    #[test]
+    fn skips_padding_pairs() {
+        let lines = vec!["43 01 01 43 00 00".to_string()];
+        let dtcs = parse_response(DtcMode::Stored, Protocol::Iso15765_4Can11Bit500k, &lines)
+            .unwrap();
+
+        assert_eq!(dtcs, vec![Dtc::decode(0x01, 0x43)]);
+    }
+}
@@ -12,4 +12,54 @@ pub enum Error {
     Read,
     TimedOut,
     Packet(&'static str),
+    /// The device responded with `NO DATA` - the ECU did not respond to the query.
+    NoData,
+    /// The device responded with `UNABLE TO CONNECT` - no ECU could be found on the bus.
+    UnableToConnect,
+    /// The device responded with `BUS BUSY`.
+    BusBusy,
+    /// The device responded with `CAN ERROR`.
+    CanError,
+    /// The device responded with `BUFFER FULL`.
+    BufferFull,
+    /// The device responded with `STOPPED` - a previous command was interrupted.
+    Stopped,
+    /// The device responded with `?` - the command was not recognised.
+    Unsupported,
+}
+
+impl Error {
+    /// Matches a line read from the device against the ELM327's known *terminal* status
+    /// responses, returning the corresponding [`Error`] variant if it is one.
+    ///
+    /// `SEARCHING...` is deliberately not matched here - it is a transient status the device
+    /// prints while it hunts for a protocol/ECU, with the real response following on a later
+    /// line, so it must not abort the read. See [`Error::is_searching`].
+    ///
+    /// # Parameters
+    ///
+    /// * `line` - The line read from the device.
+    pub(crate) fn from_status_line(line: &str) -> Option<Self> {
+        match line.trim() {
+            "NO DATA" => Some(Error::NoData),
+            "UNABLE TO CONNECT" => Some(Error::UnableToConnect),
+            "BUS BUSY" => Some(Error::BusBusy),
+            "CAN ERROR" => Some(Error::CanError),
+            "BUFFER FULL" => Some(Error::BufferFull),
+            "STOPPED" => Some(Error::Stopped),
+            "?" => Some(Error::Unsupported),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the line is the device's transient `SEARCHING...` status, printed while
+    /// it hunts for a protocol/ECU before the real response arrives. Callers should skip these
+    /// lines rather than treating them as a response or an error.
+    ///
+    /// # Parameters
+    ///
+    /// * `line` - The line read from the device.
+    pub(crate) fn is_searching(line: &str) -> bool {
+        line.trim() == "SEARCHING..."
+    }
 }
@@ -0,0 +1,178 @@
+use crate::error::{Error, Result};
+
+/// PCI (Protocol Control Information) nibble identifying an ISO-TP First Frame.
+const PCI_FIRST_FRAME: u8 = 0x1;
+/// PCI nibble identifying an ISO-TP Consecutive Frame.
+const PCI_CONSECUTIVE_FRAME: u8 = 0x2;
+
+/// Reassembles a multi-frame ISO-TP response (e.g. Mode 09 VIN, Mode 06 monitor data) spanning
+/// several CAN frames into a single contiguous buffer.
+///
+/// The [`ObdPacket`](crate::packet::ObdPacket) type packs responses into a `u64` and so caps out
+/// at 8 bytes; this buffer has no such limit.
+#[derive(Debug, Default)]
+pub struct IsoTpBuffer {
+    /// Total payload length announced by the First Frame, once seen.
+    expected_len: Option<usize>,
+    /// Payload bytes collected so far, in sequence order.
+    data: Vec<u8>,
+    /// Count of frames seen so far; taken modulo 16, it is the sequence number (0-15, rolling)
+    /// expected in the next Consecutive Frame.
+    next_seq: u8,
+}
+
+impl IsoTpBuffer {
+    /// Builds an empty reassembly buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a single response line (one CAN frame, with the arbitration ID header from `ATH1`
+    /// still present) into the buffer.
+    ///
+    /// # Parameters
+    ///
+    /// * `line` - A single line as returned by [`Elm327::read`](crate::Elm327::read).
+    pub fn feed(&mut self, line: impl AsRef<str>) -> Result<()> {
+        let parts = line.as_ref().trim().split(' ').collect::<Vec<_>>();
+
+        // The first token is the CAN arbitration ID header, the rest is frame data.
+        let frame = parts
+            .iter()
+            .skip(1)
+            .map(|b| u8::from_str_radix(b, 16).map_err(|_| Error::Conversion))
+            .collect::<Result<Vec<_>>>()?;
+
+        if frame.is_empty() {
+            return Err(Error::Packet("ISO-TP frame did not contain any data."));
+        }
+
+        match frame[0] >> 4 {
+            PCI_FIRST_FRAME => {
+                if frame.len() < 2 {
+                    return Err(Error::Packet("ISO-TP First Frame missing length byte."));
+                }
+
+                let len = (((frame[0] & 0x0F) as usize) << 8) | frame[1] as usize;
+
+                self.expected_len = Some(len);
+                self.data.extend_from_slice(&frame[2..]);
+                self.next_seq = 1;
+            }
+            PCI_CONSECUTIVE_FRAME => {
+                let seq = frame[0] & 0x0F;
+
+                if seq != self.next_seq % 16 {
+                    return Err(Error::Packet(
+                        "ISO-TP Consecutive Frame sequence number out of order.",
+                    ));
+                }
+
+                self.data.extend_from_slice(&frame[1..]);
+                self.next_seq = self.next_seq.wrapping_add(1);
+            }
+            _ => return Err(Error::Packet("Unsupported or unexpected ISO-TP frame type.")),
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` once every byte announced by the First Frame has been collected.
+    pub fn is_complete(&self) -> bool {
+        matches!(self.expected_len, Some(len) if self.data.len() >= len)
+    }
+
+    /// Consumes the buffer, returning the reassembled payload truncated to the length announced
+    /// by the First Frame.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        let len = self
+            .expected_len
+            .ok_or(Error::Packet("ISO-TP buffer never saw a First Frame."))?;
+
+        if self.data.len() < len {
+            return Err(Error::Packet("ISO-TP buffer is missing trailing frames."));
+        }
+
+        Ok(self.data[..len].to_vec())
+    }
+}
+
+/// Reassembles a full multi-frame ISO-TP response from raw response lines in one call.
+///
+/// # Parameters
+///
+/// * `lines` - Response lines as returned by [`Elm327::write`](crate::Elm327::write), with
+///   headers enabled (`ATH1`).
+pub fn reassemble(lines: &[String]) -> Result<Vec<u8>> {
+    let mut buf = IsoTpBuffer::new();
+
+    for line in lines {
+        buf.feed(line)?;
+
+        if buf.is_complete() {
+            return buf.finish();
+        }
+    }
+
+    Err(Error::Packet(
+        "ISO-TP response ended before all frames were received.",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reassembles_first_and_consecutive_frames_in_order() {
+        let lines = vec![
+            "7E8 10 14 49 02 01 00 00 3D".to_string(),
+            "7E8 21 30 30 30 30 30 30 30".to_string(),
+            "7E8 22 30 30 30 30 30 30 30".to_string(),
+        ];
+
+        let payload = reassemble(&lines).unwrap();
+
+        assert_eq!(
+            payload,
+            vec![
+                0x49, 0x02, 0x01, 0x00, 0x00, 0x3D, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30,
+                0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30,
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_on_out_of_order_consecutive_frame() {
+        let mut buf = IsoTpBuffer::new();
+        buf.feed("7E8 10 14 49 02 01 00 00 3D").unwrap();
+
+        let err = buf.feed("7E8 22 30 30 30 30 30 30 30");
+
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn errors_when_finished_before_first_frame_seen() {
+        let buf = IsoTpBuffer::new();
+
+        assert!(buf.finish().is_err());
+    }
+
+    #[test]
+    fn errors_when_trailing_frames_are_missing() {
+        let lines = vec!["7E8 10 14 49 02 01 00 00 3D".to_string()];
+
+        assert!(reassemble(&lines).is_err());
+    }
+
+    #[test]
+    fn is_complete_once_expected_length_reached() {
+        let mut buf = IsoTpBuffer::new();
+        assert!(!buf.is_complete());
+
+        buf.feed("7E8 10 06 49 02 01 00 00 3D").unwrap();
+
+        assert!(buf.is_complete());
+    }
+}
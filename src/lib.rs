@@ -1,37 +1,57 @@
+pub mod config;
+pub mod dtc;
 pub mod error;
+pub mod isotp;
 pub mod packet;
+pub mod pid;
 
 use std::time::Duration;
 
+use crate::config::{Elm327Config, Protocol};
+use crate::dtc::{Dtc, DtcMode};
 use crate::error::{Error, Result};
+use crate::packet::ObdPacket;
+use crate::pid::{Mode01Pid, Mode01Value};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
+    sync::mpsc,
     time::timeout,
 };
 use tokio_serial::{ClearBuffer, Serial, SerialPort, SerialPortSettings};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
 
 /// Interface for interacting with ELM327.
 pub struct Elm327 {
     port: Serial,
+    /// The protocol last applied via [`configure`](Elm327::configure), used to interpret
+    /// protocol-dependent responses (e.g. DTC count bytes). Defaults to [`Protocol::Automatic`]
+    /// until a config has been applied.
+    protocol: Protocol,
 }
 
 impl Elm327 {
     /// Builds a new ELM327 interface with an already connected serial port.
     /// It is recommendede to use the [`from_path`](Elm327::from_path) function instead.
     pub fn new(port: Serial) -> Self {
-        Self { port }
+        Self {
+            port,
+            protocol: Protocol::default(),
+        }
     }
 
-    /// Builds a new ELM327 interface by connecting to the given port.
+    /// Builds a new ELM327 interface by connecting to the given port, resetting the device and
+    /// applying the given [`Elm327Config`].
     ///
     /// # Parameters
     ///
     /// * `path` - Serial port path.
     /// * `settings` - Serial port settings.
+    /// * `config` - Initialisation/protocol configuration to apply once connected.
     /// * `retry` - Number of times to re-attempt reconnection before failing.
     pub async fn from_path(
         path: impl AsRef<str>,
         settings: &SerialPortSettings,
+        config: &Elm327Config,
         retry: Option<u32>,
     ) -> Result<Self> {
         let path = path.as_ref();
@@ -61,6 +81,12 @@ impl Elm327 {
             match elm.write_timeout("ATZ", Duration::from_secs(3)).await {
                 Ok(r) => {
                     dbg!(r);
+
+                    if elm.configure(config).await.is_err() {
+                        n += 1;
+                        continue;
+                    }
+
                     break Ok(elm);
                 }
                 Err(_) => {
@@ -71,6 +97,31 @@ impl Elm327 {
         }
     }
 
+    /// Applies an [`Elm327Config`] to the device, sending its ordered `AT` commands
+    /// (echo, linefeeds, spaces, headers, protocol and adaptive timing) and verifying that each
+    /// one is acknowledged with `OK` before moving on to the next.
+    ///
+    /// # Parameters
+    ///
+    /// * `config` - Configuration to apply.
+    pub async fn configure(&mut self, config: &Elm327Config) -> Result<()> {
+        for command in config.commands() {
+            let resp = self
+                .write_timeout(command, Duration::from_millis(500))
+                .await?;
+
+            if !resp.iter().any(|line| line.trim().eq_ignore_ascii_case("OK")) {
+                return Err(Error::Packet(
+                    "Device did not acknowledge configuration command.",
+                ));
+            }
+        }
+
+        self.protocol = config.protocol();
+
+        Ok(())
+    }
+
     /// Sends a command to the ELM327 device, returning a vector of strings as response.
     /// A carraige return will automatically be appended to the command.
     /// The function will only return once the `>` character has been seen or one of the steps
@@ -158,6 +209,71 @@ impl Elm327 {
         Ok(())
     }
 
+    /// Queries a standard SAE Mode 01 PID and returns the decoded physical value.
+    ///
+    /// This sends `01` followed by the PID byte (e.g. `010C` for engine RPM), locates the `41`
+    /// response line, strips the mode/PID echo and decodes the remaining data bytes using
+    /// [`ObdPacket::get`](crate::packet::ObdPacket::get).
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The PID to query.
+    pub async fn query_pid(&mut self, pid: Mode01Pid) -> Result<Mode01Value> {
+        let command = format!("01{:02X}", pid.pid());
+        let resp = self.write(command).await?;
+
+        pid::parse_response(pid, &resp)
+    }
+
+    /// Reads the stored (Mode `03`) Diagnostic Trouble Codes from the device.
+    pub async fn read_stored_dtcs(&mut self) -> Result<Vec<Dtc>> {
+        self.read_dtcs(DtcMode::Stored).await
+    }
+
+    /// Reads the pending (Mode `07`) Diagnostic Trouble Codes from the device.
+    pub async fn read_pending_dtcs(&mut self) -> Result<Vec<Dtc>> {
+        self.read_dtcs(DtcMode::Pending).await
+    }
+
+    /// Reads the permanent (Mode `0A`) Diagnostic Trouble Codes from the device.
+    pub async fn read_permanent_dtcs(&mut self) -> Result<Vec<Dtc>> {
+        self.read_dtcs(DtcMode::Permanent).await
+    }
+
+    /// Issues the given DTC query mode and decodes the response into a list of [`Dtc`]s.
+    ///
+    /// # Parameters
+    ///
+    /// * `mode` - The DTC mode to query.
+    async fn read_dtcs(&mut self, mode: DtcMode) -> Result<Vec<Dtc>> {
+        let resp = self.write(mode.command()).await?;
+
+        dtc::parse_response(mode, self.protocol, &resp)
+    }
+
+    /// Sends a command expected to return a multi-frame ISO-TP response (e.g. Mode `09` VIN,
+    /// Mode `06` monitor data) and reassembles the frames into a single contiguous buffer.
+    ///
+    /// Requires headers to be enabled (`ATH1`) so that each line starts with the arbitration ID
+    /// header followed by the ISO-TP PCI byte used to detect First and Consecutive Frames; see
+    /// [`isotp::reassemble`].
+    ///
+    /// # Parameters
+    ///
+    /// * `command` - Command to send.
+    pub async fn write_multiframe(&mut self, command: impl AsRef<str>) -> Result<Vec<u8>> {
+        let resp = self.write(command).await?;
+
+        isotp::reassemble(&resp)
+    }
+
+    /// Clears all stored and pending Diagnostic Trouble Codes using Mode `04`.
+    pub async fn clear_dtcs(&mut self) -> Result<()> {
+        self.write("04").await?;
+
+        Ok(())
+    }
+
     /// Runs the 'Monitor all' command on the ELM327 device.
     ///
     /// You must provide a function `on_str` to be called each time a string is read from the
@@ -176,6 +292,70 @@ impl Elm327 {
         resp
     }
 
+    /// Runs the 'Monitor all' command on the ELM327 device, yielding each line as a decoded
+    /// [`ObdPacket`] as soon as it arrives, rather than buffering the whole session into a
+    /// `Vec<String>`.
+    ///
+    /// This consumes the device, spawning a task that accumulates bytes off the serial port,
+    /// splits them on `\r`/`\n` into complete lines, and pushes a decoded packet per line down a
+    /// [`tokio_stream`] channel, skipping the transient `SEARCHING...` status. Once monitoring has
+    /// started, the stream ends when the device prints `>`, reading from the port errors, or the
+    /// receiving end is dropped; in each of these cases the stop command is sent to the device
+    /// before the task exits, mirroring [`monitor_all`](Elm327::monitor_all) so the device never
+    /// gets left in monitor mode. If sending `ATMA` itself fails, that error is yielded and the
+    /// stream ends without a stop command. Useful for long-running CAN sniffing where collecting a
+    /// giant `Vec` is impractical.
+    pub fn monitor_all_stream(mut self) -> impl Stream<Item = Result<ObdPacket>> {
+        let (tx, rx) = mpsc::channel(32);
+
+        tokio::spawn(async move {
+            if let Err(e) = self.write_no_resp("ATMA").await {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+
+            let mut buf = vec![];
+            let mut char = [0u8];
+
+            loop {
+                if self.port.read(&mut char).await.is_err() {
+                    let _ = tx.send(Err(Error::Read)).await;
+                    break;
+                }
+
+                match char[0] {
+                    b'\r' | b'\n' | b'>' => {
+                        if !buf.is_empty() {
+                            if let Ok(line) = String::from_utf8(std::mem::take(&mut buf)) {
+                                if !Error::is_searching(&line) {
+                                    let item = match Error::from_status_line(&line) {
+                                        Some(e) => Err(e),
+                                        None => ObdPacket::new(&line),
+                                    };
+
+                                    if tx.send(item).await.is_err() {
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+
+                        if char[0] == b'>' {
+                            break;
+                        }
+                    }
+                    _ => buf.push(char[0]),
+                }
+            }
+
+            // Stop monitoring, whether reading ended because the device sent '>', the port
+            // errored, or the receiving end of the stream was dropped.
+            let _ = self.write_no_resp("").await;
+        });
+
+        ReceiverStream::new(rx)
+    }
+
     /// Reads from the serial port. Returns a vector of strings which have been read from the
     /// device. Returns once the '>' character has been read from the device.
     ///
@@ -185,6 +365,12 @@ impl Elm327 {
     /// device until it sees a '>' character. If it returns `false`, it will return a vector
     /// of strings that it has seen.
     ///
+    /// If a line matches one of the ELM327's terminal textual status responses (`NO DATA`,
+    /// `UNABLE TO CONNECT`, `BUS BUSY`, `CAN ERROR`, `BUFFER FULL`, `STOPPED` or `?`), the
+    /// corresponding [`Error`] variant is returned immediately instead. The transient
+    /// `SEARCHING...` status is skipped rather than erroring, since the real response follows it
+    /// on a later line.
+    ///
     /// # Parameters
     ///
     /// * `on_str` - The function to call when a string has been seen.
@@ -204,10 +390,16 @@ impl Elm327 {
                 b'\r' | b'\n' | b'>' => {
                     if !buf.is_empty() {
                         if let Ok(str) = String::from_utf8(buf) {
-                            strs.push(str.clone());
+                            if !Error::is_searching(&str) {
+                                if let Some(e) = Error::from_status_line(&str) {
+                                    return Err(e);
+                                }
+
+                                strs.push(str.clone());
 
-                            if !on_str(&str) {
-                                break;
+                                if !on_str(&str) {
+                                    break;
+                                }
                             }
                         }
                     }
@@ -0,0 +1,142 @@
+use crate::error::{Error, Result};
+use crate::packet::ObdPacket;
+
+/// A standard SAE Mode 01 PID ("show current data") supported by
+/// [`Elm327::query_pid`](crate::Elm327::query_pid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode01Pid {
+    /// PID `0x0C` - Engine RPM.
+    EngineRpm,
+    /// PID `0x0D` - Vehicle speed, in km/h.
+    VehicleSpeed,
+    /// PID `0x05` - Engine coolant temperature, in degrees Celsius.
+    CoolantTemp,
+    /// PID `0x11` - Throttle position, as a percentage.
+    ThrottlePosition,
+}
+
+impl Mode01Pid {
+    /// Returns the PID byte used to query this value, e.g. `0x0C` for [`EngineRpm`](Mode01Pid::EngineRpm).
+    pub(crate) fn pid(&self) -> u8 {
+        match self {
+            Mode01Pid::EngineRpm => 0x0C,
+            Mode01Pid::VehicleSpeed => 0x0D,
+            Mode01Pid::CoolantTemp => 0x05,
+            Mode01Pid::ThrottlePosition => 0x11,
+        }
+    }
+}
+
+/// The decoded physical value returned by [`Elm327::query_pid`](crate::Elm327::query_pid), one
+/// variant per supported [`Mode01Pid`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode01Value {
+    /// Engine RPM, decoded from PID `0x0C`.
+    EngineRpm(f64),
+    /// Vehicle speed, in km/h, decoded from PID `0x0D`.
+    VehicleSpeed(u64),
+    /// Engine coolant temperature, in degrees Celsius, decoded from PID `0x05`.
+    CoolantTemp(i64),
+    /// Throttle position, as a percentage, decoded from PID `0x11`.
+    ThrottlePosition(f64),
+}
+
+impl Mode01Value {
+    /// Decodes the data bytes (`A`, `B`, ...) following the `41 PID` echo into a physical value.
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The PID that was queried, used to select the decoding formula.
+    /// * `packet` - The data bytes, loaded into an [`ObdPacket`].
+    fn decode(pid: Mode01Pid, packet: &ObdPacket) -> Result<Self> {
+        match pid {
+            Mode01Pid::EngineRpm => {
+                let a = packet.get(56, 63)?;
+                let b = packet.get(48, 55)?;
+                Ok(Mode01Value::EngineRpm(((256 * a + b) as f64) / 4.0))
+            }
+            Mode01Pid::VehicleSpeed => {
+                let a = packet.get(56, 63)?;
+                Ok(Mode01Value::VehicleSpeed(a))
+            }
+            Mode01Pid::CoolantTemp => {
+                let a = packet.get(56, 63)?;
+                Ok(Mode01Value::CoolantTemp(a as i64 - 40))
+            }
+            Mode01Pid::ThrottlePosition => {
+                let a = packet.get(56, 63)?;
+                Ok(Mode01Value::ThrottlePosition((a as f64) * 100.0 / 255.0))
+            }
+        }
+    }
+}
+
+/// Parses the response lines from a Mode 01 query, locating the line beginning with the `41`
+/// response prefix, stripping the mode/PID echo bytes and loading the remainder into an
+/// [`ObdPacket`].
+///
+/// # Parameters
+///
+/// * `pid` - The PID that was queried.
+/// * `lines` - Response lines as returned by [`Elm327::write`](crate::Elm327::write).
+pub(crate) fn parse_response(pid: Mode01Pid, lines: &[String]) -> Result<Mode01Value> {
+    let line = lines
+        .iter()
+        .find(|line| line.trim().to_uppercase().starts_with("41"))
+        .ok_or(Error::Packet("No Mode 01 response line found."))?;
+
+    let parts = line.trim().split(' ').skip(2).collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        return Err(Error::Packet("Mode 01 response did not contain data bytes."));
+    }
+
+    let packet = ObdPacket::new(parts.join(" "))?;
+
+    Mode01Value::decode(pid, &packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_engine_rpm() {
+        let lines = vec!["41 0C 1A F8".to_string()];
+        let value = parse_response(Mode01Pid::EngineRpm, &lines).unwrap();
+
+        assert_eq!(value, Mode01Value::EngineRpm(1726.0));
+    }
+
+    #[test]
+    fn decodes_vehicle_speed() {
+        let lines = vec!["41 0D 50".to_string()];
+        let value = parse_response(Mode01Pid::VehicleSpeed, &lines).unwrap();
+
+        assert_eq!(value, Mode01Value::VehicleSpeed(0x50));
+    }
+
+    #[test]
+    fn decodes_coolant_temp() {
+        let lines = vec!["41 05 7B".to_string()];
+        let value = parse_response(Mode01Pid::CoolantTemp, &lines).unwrap();
+
+        assert_eq!(value, Mode01Value::CoolantTemp(0x7B - 40));
+    }
+
+    #[test]
+    fn decodes_throttle_position() {
+        let lines = vec!["41 11 FF".to_string()];
+        let value = parse_response(Mode01Pid::ThrottlePosition, &lines).unwrap();
+
+        assert_eq!(value, Mode01Value::ThrottlePosition(100.0));
+    }
+
+    #[test]
+    fn errors_when_no_response_line_found() {
+        let lines = vec!["NO DATA".to_string()];
+        let err = parse_response(Mode01Pid::EngineRpm, &lines);
+
+        assert!(err.is_err());
+    }
+}